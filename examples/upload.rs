@@ -0,0 +1,91 @@
+#![deny(warnings)]
+#![warn(rust_2018_idioms)]
+use std::env;
+
+use futures_util::TryStreamExt;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use hyper::{header, Method, Request};
+use tokio::fs::File;
+use tokio::io::{self, AsyncWriteExt as _};
+use tokio::net::TcpStream;
+use tokio_util::io::ReaderStream;
+
+#[path = "../benches/support/mod.rs"]
+mod support;
+use support::TokioIo;
+
+// A simple type alias so as to DRY.
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    pretty_env_logger::init();
+
+    // Some simple CLI args requirements...
+    let (url, path) = match (env::args().nth(1), env::args().nth(2)) {
+        (Some(url), Some(path)) => (url, path),
+        _ => {
+            println!("Usage: upload <url> <file>");
+            return Ok(());
+        }
+    };
+
+    let url = url.parse::<hyper::Uri>().unwrap();
+    if url.scheme_str() != Some("http") {
+        println!("This example only works with 'http' URLs.");
+        return Ok(());
+    }
+
+    upload_file(url, &path).await
+}
+
+async fn upload_file(url: hyper::Uri, path: &str) -> Result<()> {
+    let host = url.host().expect("uri has no host");
+    let port = url.port_u16().unwrap_or(80);
+    let addr = format!("{}:{}", host, port);
+    let stream = TcpStream::connect(addr).await?;
+    let io = TokioIo::new(stream);
+
+    let (mut sender, conn) = hyper::client::conn::http1::handshake(io).await?;
+    tokio::task::spawn(async move {
+        if let Err(err) = conn.await {
+            println!("Connection failed: {:?}", err);
+        }
+    });
+
+    let file = File::open(path).await?;
+    let len = file.metadata().await?.len();
+
+    // Any `AsyncRead` can be turned into a body by wrapping it in a
+    // `ReaderStream`. Such a body doesn't know its own length, so hyper
+    // would send it with `transfer-encoding: chunked`. Since the size of
+    // the file is known, set a `content-length` instead, and hyper will
+    // frame the body with that.
+    let body = StreamBody::new(ReaderStream::new(file).map_ok(Frame::data));
+
+    let authority = url.authority().unwrap().clone();
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(url.path())
+        .header(header::HOST, authority.as_str())
+        .header(header::CONTENT_LENGTH, len)
+        .body(body)?;
+
+    let mut res = sender.send_request(req).await?;
+
+    println!("Response: {}", res.status());
+    println!("Headers: {:#?}\n", res.headers());
+
+    while let Some(next) = res.frame().await {
+        let frame = next?;
+        if let Some(chunk) = frame.data_ref() {
+            io::stdout().write_all(chunk).await?;
+        }
+    }
+
+    println!("\n\nDone!");
+
+    Ok(())
+}
@@ -39,6 +39,10 @@ type TrailersSender = oneshot::Sender<HeaderMap>;
 /// `Incoming` is returned to you in responses. Similarly, when operating with the hyper server,
 /// it is provided within requests.
 ///
+/// If the length of the body is known, such as from a `content-length`
+/// header, [`Body::size_hint`] reports exactly how many bytes have not
+/// been read yet. Otherwise, no upper bound is given.
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -504,6 +508,12 @@ mod tests {
 
         eq(Incoming::channel().1, SizeHint::new(), "channel");
 
+        eq(
+            Incoming::new_channel(DecodedLength::CLOSE_DELIMITED, /*wanter =*/ false).1,
+            SizeHint::new(),
+            "channel close-delimited",
+        );
+
         eq(
             Incoming::new_channel(DecodedLength::new(4), /*wanter =*/ false).1,
             SizeHint::with_exact(4),
@@ -511,6 +521,28 @@ mod tests {
         );
     }
 
+    #[cfg(all(not(miri), feature = "http1"))]
+    #[tokio::test]
+    async fn size_hint_counts_down_as_data_is_read() {
+        let (mut tx, mut rx) =
+            Incoming::new_channel(DecodedLength::new(10), /*wanter =*/ false);
+
+        tx.try_send_data("hello".into()).expect("send 1");
+
+        let chunk = rx
+            .frame()
+            .await
+            .expect("item 1")
+            .expect("chunk 1")
+            .into_data()
+            .unwrap();
+        assert_eq!(chunk, "hello");
+
+        let hint = rx.size_hint();
+        assert_eq!(hint.lower(), 5);
+        assert_eq!(hint.upper(), Some(5));
+    }
+
     #[cfg(not(miri))]
     #[tokio::test]
     async fn channel_abort() {
@@ -556,6 +588,16 @@ mod tests {
         assert_eq!(chunk2, "chunk 2");
     }
 
+    #[cfg(not(miri))]
+    #[tokio::test]
+    async fn empty_is_end_stream() {
+        let mut body = Incoming::empty();
+
+        assert!(body.is_end_stream(), "before polling");
+        assert!(body.frame().await.is_none());
+        assert!(body.is_end_stream(), "after polling");
+    }
+
     #[cfg(not(miri))]
     #[tokio::test]
     async fn channel_empty() {
@@ -110,6 +110,13 @@ mod tests {
         assert_eq!(DATE_VALUE_LENGTH, "Sun, 06 Nov 1994 08:49:37 GMT".len());
     }
 
+    #[test]
+    fn test_date_is_valid_http_date() {
+        let date = CachedDate::new();
+        let s = str::from_utf8(date.buffer()).expect("date is ascii");
+        httpdate::parse_http_date(s).expect("date is a valid HTTP-date");
+    }
+
     #[cfg(feature = "nightly")]
     #[bench]
     fn bench_date_check(b: &mut Bencher) {
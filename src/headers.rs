@@ -36,6 +36,13 @@ pub(super) fn content_length_parse(value: &HeaderValue) -> Option<u64> {
     from_digits(value.as_bytes())
 }
 
+#[cfg(all(feature = "http1", feature = "server"))]
+pub(super) fn content_length_parse_list(value: &HeaderValue) -> Option<u64> {
+    // A single field line may also carry a comma-separated list of values.
+    // Just like with multiple lines, that's fine as long as they all agree.
+    content_length_parse_line(value, None)
+}
+
 #[cfg(any(feature = "client", all(feature = "server", feature = "http2")))]
 pub(super) fn content_length_parse_all(headers: &HeaderMap) -> Option<u64> {
     content_length_parse_all_values(headers.get_all(CONTENT_LENGTH).into_iter())
@@ -49,19 +56,20 @@ pub(super) fn content_length_parse_all_values(values: ValueIter<'_, HeaderValue>
 
     let mut content_length: Option<u64> = None;
     for h in values {
-        if let Ok(line) = h.to_str() {
-            for v in line.split(',') {
-                if let Some(n) = from_digits(v.trim().as_bytes()) {
-                    if content_length.is_none() {
-                        content_length = Some(n)
-                    } else if content_length != Some(n) {
-                        return None;
-                    }
-                } else {
-                    return None;
-                }
-            }
-        } else {
+        content_length = Some(content_length_parse_line(h, content_length)?);
+    }
+
+    content_length
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+fn content_length_parse_line(h: &HeaderValue, mut content_length: Option<u64>) -> Option<u64> {
+    let line = h.to_str().ok()?;
+    for v in line.split(',') {
+        let n = from_digits(v.trim().as_bytes())?;
+        if content_length.is_none() {
+            content_length = Some(n)
+        } else if content_length != Some(n) {
             return None;
         }
     }
@@ -4,12 +4,12 @@ use std::marker::{PhantomData, Unpin};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 #[cfg(feature = "server")]
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::rt::{Read, Write};
 use bytes::{Buf, Bytes};
 use futures_util::ready;
-use http::header::{HeaderValue, CONNECTION, TE};
+use http::header::{HeaderValue, CONNECTION, CONTENT_LENGTH, TE};
 use http::{HeaderMap, Method, Version};
 use http_body::Frame;
 use httparse::ParserConfig;
@@ -57,12 +57,18 @@ where
                 h1_parser_config: ParserConfig::default(),
                 h1_max_headers: None,
                 #[cfg(feature = "server")]
+                h1_max_uri_len: None,
+                #[cfg(feature = "server")]
                 h1_header_read_timeout: None,
                 #[cfg(feature = "server")]
                 h1_header_read_timeout_fut: None,
                 #[cfg(feature = "server")]
                 h1_header_read_timeout_running: false,
                 #[cfg(feature = "server")]
+                h1_keep_alive_timeout: None,
+                #[cfg(feature = "server")]
+                h1_keep_alive_timeout_fut: None,
+                #[cfg(feature = "server")]
                 date_header: true,
                 #[cfg(feature = "server")]
                 timer: Time::Empty,
@@ -104,6 +110,11 @@ where
         self.io.set_max_buf_size(max);
     }
 
+    #[cfg(feature = "server")]
+    pub(crate) fn set_read_buf_init_size(&mut self, sz: usize) {
+        self.io.set_read_buf_init_size(sz);
+    }
+
     #[cfg(feature = "client")]
     pub(crate) fn set_read_buf_exact_size(&mut self, sz: usize) {
         self.io.set_read_buf_exact_size(sz);
@@ -140,11 +151,21 @@ where
         self.state.h1_max_headers = Some(val);
     }
 
+    #[cfg(feature = "server")]
+    pub(crate) fn set_http1_max_uri_len(&mut self, val: usize) {
+        self.state.h1_max_uri_len = Some(val);
+    }
+
     #[cfg(feature = "server")]
     pub(crate) fn set_http1_header_read_timeout(&mut self, val: Duration) {
         self.state.h1_header_read_timeout = Some(val);
     }
 
+    #[cfg(feature = "server")]
+    pub(crate) fn set_http1_keep_alive_timeout(&mut self, val: Duration) {
+        self.state.h1_keep_alive_timeout = Some(val);
+    }
+
     #[cfg(feature = "server")]
     pub(crate) fn set_allow_half_close(&mut self) {
         self.state.allow_half_close = true;
@@ -209,7 +230,7 @@ where
         debug_assert!(self.can_read_head());
         trace!("Conn::read_head");
 
-        let msg = match ready!(self.io.parse::<T>(
+        let msg = match self.io.parse::<T>(
             cx,
             ParseContext {
                 cached_headers: &mut self.state.cached_headers,
@@ -217,6 +238,8 @@ where
                 h1_parser_config: self.state.h1_parser_config.clone(),
                 h1_max_headers: self.state.h1_max_headers,
                 #[cfg(feature = "server")]
+                h1_max_uri_len: self.state.h1_max_uri_len,
+                #[cfg(feature = "server")]
                 h1_header_read_timeout: self.state.h1_header_read_timeout,
                 #[cfg(feature = "server")]
                 h1_header_read_timeout_fut: &mut self.state.h1_header_read_timeout_fut,
@@ -230,12 +253,27 @@ where
                 h09_responses: self.state.h09_responses,
                 #[cfg(feature = "ffi")]
                 on_informational: &mut self.state.on_informational,
+            },
+        ) {
+            Poll::Ready(Ok(msg)) => msg,
+            Poll::Ready(Err(e)) => return self.on_read_head_error(e),
+            Poll::Pending => {
+                #[cfg(feature = "server")]
+                if self.poll_keep_alive_timeout(cx).is_ready() {
+                    debug!("keep-alive timeout elapsed, closing idle connection");
+                    self.close_read();
+                    self.close_write();
+                    return Poll::Ready(None);
+                }
+                return Poll::Pending;
             }
-        )) {
-            Ok(msg) => msg,
-            Err(e) => return self.on_read_head_error(e),
         };
 
+        #[cfg(feature = "server")]
+        {
+            self.state.h1_keep_alive_timeout_fut = None;
+        }
+
         // Note: don't deconstruct `msg` into local variables, it appears
         // the optimizer doesn't remove the extra copies.
 
@@ -294,6 +332,25 @@ where
         Poll::Ready(Some(Ok((msg.head, msg.decode, wants))))
     }
 
+    // Only a connection that has finished a message and is waiting on the
+    // next one, without having received any of it yet, is idle.
+    #[cfg(feature = "server")]
+    fn poll_keep_alive_timeout(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let dur = match self.state.h1_keep_alive_timeout {
+            Some(dur) if self.state.is_idle() && self.io.read_buf().is_empty() => dur,
+            _ => {
+                self.state.h1_keep_alive_timeout_fut = None;
+                return Poll::Pending;
+            }
+        };
+        let timer = &self.state.timer;
+        self.state
+            .h1_keep_alive_timeout_fut
+            .get_or_insert_with(|| timer.sleep_until(Instant::now() + dur))
+            .as_mut()
+            .poll(cx)
+    }
+
     fn on_read_head_error<Z>(&mut self, e: crate::Error) -> Poll<Option<crate::Result<Z>>> {
         // If we are currently waiting on a message, then an empty
         // message should be reported as an error. If not, it is just
@@ -574,7 +631,7 @@ where
             self.state.busy();
         }
 
-        self.enforce_version(&mut head);
+        self.enforce_version(&mut head, body.as_ref());
 
         let buf = self.io.headers_buf();
         match super::role::encode_headers::<T>(
@@ -612,7 +669,7 @@ where
     }
 
     // Fix keep-alive when Connection: keep-alive header is not present
-    fn fix_keep_alive(&mut self, head: &mut MessageHead<T::Outgoing>) {
+    fn fix_keep_alive(&mut self, head: &mut MessageHead<T::Outgoing>, body: Option<&BodyLength>) {
         let outgoing_is_keep_alive = head
             .headers
             .get(CONNECTION)
@@ -624,9 +681,16 @@ where
                 // disable keep-alive so the server closes the connection
                 Version::HTTP_10 => self.state.disable_keep_alive(),
                 // If response is version 1.1 and keep-alive is wanted, add
-                // Connection: keep-alive header when not present
+                // Connection: keep-alive header when not present, unless the
+                // body of unknown length (and no content-length) can only be
+                // delimited by closing
                 Version::HTTP_11 => {
-                    if self.state.wants_keep_alive() {
+                    let close_delimited = matches!(body, Some(BodyLength::Unknown))
+                        && !head.headers.contains_key(CONTENT_LENGTH)
+                        && T::may_close_delimit(&self.state.method, head);
+                    if close_delimited {
+                        self.state.disable_keep_alive();
+                    } else if self.state.wants_keep_alive() {
                         head.headers
                             .insert(CONNECTION, HeaderValue::from_static("keep-alive"));
                     }
@@ -638,10 +702,10 @@ where
 
     // If we know the remote speaks an older version, we try to fix up any messages
     // to work with our older peer.
-    fn enforce_version(&mut self, head: &mut MessageHead<T::Outgoing>) {
+    fn enforce_version(&mut self, head: &mut MessageHead<T::Outgoing>, body: Option<&BodyLength>) {
         if let Version::HTTP_10 = self.state.version {
             // Fixes response or connection when keep-alive header is not present
-            self.fix_keep_alive(head);
+            self.fix_keep_alive(head, body);
             // If the remote only knows HTTP/1.0, we should force ourselves
             // to do only speak HTTP/1.0 as well.
             head.version = Version::HTTP_10;
@@ -876,12 +940,18 @@ struct State {
     h1_parser_config: ParserConfig,
     h1_max_headers: Option<usize>,
     #[cfg(feature = "server")]
+    h1_max_uri_len: Option<usize>,
+    #[cfg(feature = "server")]
     h1_header_read_timeout: Option<Duration>,
     #[cfg(feature = "server")]
     h1_header_read_timeout_fut: Option<Pin<Box<dyn Sleep>>>,
     #[cfg(feature = "server")]
     h1_header_read_timeout_running: bool,
     #[cfg(feature = "server")]
+    h1_keep_alive_timeout: Option<Duration>,
+    #[cfg(feature = "server")]
+    h1_keep_alive_timeout_fut: Option<Pin<Box<dyn Sleep>>>,
+    #[cfg(feature = "server")]
     date_header: bool,
     #[cfg(feature = "server")]
     timer: Time,
@@ -1073,6 +1143,13 @@ impl State {
         if !T::should_read_first() {
             self.notify_read = true;
         }
+
+        // Likewise, a Server with a keep-alive timeout needs to poll reading
+        // again, so that the idle timer gets started.
+        #[cfg(feature = "server")]
+        if self.h1_keep_alive_timeout.is_some() {
+            self.notify_read = true;
+        }
     }
 
     fn is_idle(&self) -> bool {
@@ -842,8 +842,12 @@ mod tests {
         read_err("1 A\r\n", InvalidInput).await;
         read_err("1;no CRLF", UnexpectedEof).await;
         read_err("1;reject\nnewlines\r\n", InvalidData).await;
+        // Large sizes are fine, since the body is never buffered up front
+        assert_eq!(0x100_0000, read("1000000\r\n").await);
+        assert_eq!(u64::MAX, read("FFFFFFFFFFFFFFFF\r\n").await);
         // Overflow
         read_err("f0000000000000003\r\n", InvalidData).await;
+        read_err("10000000000000000\r\n", InvalidData).await;
     }
 
     #[cfg(not(miri))]
@@ -1047,6 +1051,37 @@ mod tests {
         all_async_cases(content, content, Decoder::eof()).await;
     }
 
+    #[cfg(not(miri))]
+    #[tokio::test]
+    async fn test_read_chunked_buffered_leaves_next_message() {
+        use super::super::io::{Buffered, Cursor};
+        use crate::common::io::Compat;
+
+        let content = b"3\r\nfoo\r\n3\r\nbar\r\n0\r\n\r\nGET / HTTP/1.1\r\n\r\n";
+        let mock = tokio_test::io::Builder::new().read(content).build();
+        let mut buffered = Buffered::<_, Cursor<Vec<u8>>>::new(Compat::new(mock));
+
+        let mut decoder = Decoder::chunked(None, None);
+        let mut outs = Vec::new();
+        loop {
+            let buf = decoder
+                .decode_fut(&mut buffered)
+                .await
+                .expect("decode")
+                .into_data()
+                .expect("unknown frame type");
+            if buf.is_empty() {
+                break;
+            }
+            outs.extend_from_slice(&buf);
+        }
+
+        assert_eq!(outs, b"foobar");
+        // the whole message was pulled into the read buffer in one go, but
+        // nothing past the end of the body may have been consumed.
+        assert_eq!(buffered.read_buf(), b"GET / HTTP/1.1\r\n\r\n");
+    }
+
     #[cfg(all(feature = "nightly", not(miri)))]
     #[bench]
     fn bench_decode_chunked_1kb(b: &mut test::Bencher) {
@@ -1076,6 +1111,49 @@ mod tests {
         });
     }
 
+    #[cfg(all(feature = "nightly", not(miri)))]
+    #[bench]
+    fn bench_decode_chunked_many_1kb(b: &mut test::Bencher) {
+        use super::super::io::{Buffered, Cursor};
+        use crate::common::io::Compat;
+
+        let rt = new_runtime();
+
+        const LEN: usize = 1024;
+        const CHUNKS: usize = 256;
+        let mut vec = Vec::new();
+        for _ in 0..CHUNKS {
+            vec.extend(format!("{:x}\r\n", LEN).as_bytes());
+            vec.extend(&[0; LEN][..]);
+            vec.extend(b"\r\n");
+        }
+        vec.extend(b"0\r\n\r\n");
+
+        b.bytes = (LEN * CHUNKS) as u64;
+
+        b.iter(|| {
+            let mut decoder = Decoder::chunked(None, None);
+            rt.block_on(async {
+                let mock = tokio_test::io::Builder::new().read(&vec).build();
+                let mut buffered = Buffered::<_, Cursor<Vec<u8>>>::new(Compat::new(mock));
+                let mut total = 0;
+                loop {
+                    let chunk = decoder
+                        .decode_fut(&mut buffered)
+                        .await
+                        .unwrap()
+                        .into_data()
+                        .unwrap();
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    total += chunk.len();
+                }
+                assert_eq!(total, LEN * CHUNKS);
+            });
+        });
+    }
+
     #[cfg(all(feature = "nightly", not(miri)))]
     #[bench]
     fn bench_decode_length_1kb(b: &mut test::Bencher) {
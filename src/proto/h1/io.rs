@@ -19,6 +19,10 @@ pub(crate) const INIT_BUFFER_SIZE: usize = 8192;
 /// The minimum value that can be set to max buffer size.
 pub(crate) const MINIMUM_MAX_BUFFER_SIZE: usize = INIT_BUFFER_SIZE;
 
+/// The minimum value that can be set as the initial read buffer size.
+#[cfg(feature = "server")]
+pub(crate) const MINIMUM_READ_BUF_INIT_SIZE: usize = 64;
+
 /// The default maximum read buffer size. If the buffer gets this big and
 /// a message is still not complete, a `TooLarge` error is triggered.
 // Note: if this changes, update server::conn::Http::max_buf_size docs.
@@ -93,6 +97,11 @@ where
         self.write_buf.max_buf_size = max;
     }
 
+    #[cfg(feature = "server")]
+    pub(crate) fn set_read_buf_init_size(&mut self, sz: usize) {
+        self.read_buf_strategy = self.read_buf_strategy.with_init(sz);
+    }
+
     #[cfg(feature = "client")]
     pub(crate) fn set_read_buf_exact_size(&mut self, sz: usize) {
         self.read_buf_strategy = ReadStrategy::Exact(sz);
@@ -184,6 +193,8 @@ where
                     h1_parser_config: parse_ctx.h1_parser_config.clone(),
                     h1_max_headers: parse_ctx.h1_max_headers,
                     #[cfg(feature = "server")]
+                    h1_max_uri_len: parse_ctx.h1_max_uri_len,
+                    #[cfg(feature = "server")]
                     h1_header_read_timeout: parse_ctx.h1_header_read_timeout,
                     #[cfg(feature = "server")]
                     h1_header_read_timeout_fut: parse_ctx.h1_header_read_timeout_fut,
@@ -376,6 +387,7 @@ enum ReadStrategy {
     Adaptive {
         decrease_now: bool,
         next: usize,
+        init: usize,
         max: usize,
     },
     #[cfg(feature = "client")]
@@ -387,10 +399,29 @@ impl ReadStrategy {
         ReadStrategy::Adaptive {
             decrease_now: false,
             next: INIT_BUFFER_SIZE,
+            init: INIT_BUFFER_SIZE,
             max,
         }
     }
 
+    /// Start reading with `init` bytes instead, and never shrink below it.
+    #[cfg(feature = "server")]
+    fn with_init(self, init: usize) -> ReadStrategy {
+        match self {
+            ReadStrategy::Adaptive { max, .. } => {
+                let init = cmp::min(init, max);
+                ReadStrategy::Adaptive {
+                    decrease_now: false,
+                    next: init,
+                    init,
+                    max,
+                }
+            }
+            #[cfg(feature = "client")]
+            exact @ ReadStrategy::Exact(_) => exact,
+        }
+    }
+
     fn next(&self) -> usize {
         match *self {
             ReadStrategy::Adaptive { next, .. } => next,
@@ -412,8 +443,8 @@ impl ReadStrategy {
             ReadStrategy::Adaptive {
                 ref mut decrease_now,
                 ref mut next,
+                init,
                 max,
-                ..
             } => {
                 if bytes_read >= *next {
                     *next = cmp::min(incr_power_of_two(*next), max);
@@ -422,7 +453,7 @@ impl ReadStrategy {
                     let decr_to = prev_power_of_two(*next);
                     if bytes_read < decr_to {
                         if *decrease_now {
-                            *next = cmp::max(decr_to, INIT_BUFFER_SIZE);
+                            *next = cmp::max(decr_to, init);
                             *decrease_now = false;
                         } else {
                             // Decreasing is a two "record" process.
@@ -726,6 +757,7 @@ mod tests {
                 req_method: &mut None,
                 h1_parser_config: Default::default(),
                 h1_max_headers: None,
+                h1_max_uri_len: None,
                 h1_header_read_timeout: None,
                 h1_header_read_timeout_fut: &mut None,
                 h1_header_read_timeout_running: &mut false,
@@ -828,6 +860,28 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "server")]
+    #[test]
+    fn read_strategy_adaptive_init() {
+        let mut strategy = ReadStrategy::default().with_init(1024);
+        assert_eq!(strategy.next(), 1024);
+
+        strategy.record(1024);
+        assert_eq!(strategy.next(), 2048, "still grows from init");
+
+        strategy.record(1);
+        strategy.record(1);
+        assert_eq!(strategy.next(), 1024, "decrements back to init");
+
+        strategy.record(1);
+        strategy.record(1);
+        assert_eq!(strategy.next(), 1024, "doesn't decrement under init");
+
+        let max = strategy.max();
+        let strategy = ReadStrategy::default().with_init(max + 1);
+        assert_eq!(strategy.next(), max, "init never goes over max");
+    }
+
     #[test]
     fn read_strategy_adaptive_max_fuzz() {
         fn fuzz(max: usize) {
@@ -18,6 +18,8 @@ pub(crate) use self::dispatch::Dispatcher;
 pub(crate) use self::encode::{EncodedBuf, Encoder};
 //TODO: move out of h1::io
 pub(crate) use self::io::MINIMUM_MAX_BUFFER_SIZE;
+#[cfg(feature = "server")]
+pub(crate) use self::io::MINIMUM_READ_BUF_INIT_SIZE;
 
 mod conn;
 mod decode;
@@ -59,6 +61,12 @@ pub(crate) trait Http1Transaction {
         Self::is_server()
     }
 
+    /// Whether an outgoing body of unknown length, without chunked
+    /// encoding, would be delimited by closing the connection.
+    fn may_close_delimit(_method: &Option<Method>, _head: &MessageHead<Self::Outgoing>) -> bool {
+        false
+    }
+
     fn update_date() {}
 }
 
@@ -80,6 +88,8 @@ pub(crate) struct ParseContext<'a> {
     h1_parser_config: ParserConfig,
     h1_max_headers: Option<usize>,
     #[cfg(feature = "server")]
+    h1_max_uri_len: Option<usize>,
+    #[cfg(feature = "server")]
     h1_header_read_timeout: Option<Duration>,
     #[cfg(feature = "server")]
     h1_header_read_timeout_fut: &'a mut Option<Pin<Box<dyn Sleep>>>,
@@ -32,8 +32,9 @@ use crate::proto::{BodyLength, MessageHead, RequestLine};
 
 pub(crate) const DEFAULT_MAX_HEADERS: usize = 100;
 const AVERAGE_HEADER_SIZE: usize = 30; // totally scientific
+// http::Uri rejects anything longer, so this is also the ceiling.
 #[cfg(feature = "server")]
-const MAX_URI_LEN: usize = (u16::MAX - 1) as usize;
+const DEFAULT_MAX_URI_LEN: usize = (u16::MAX - 1) as usize;
 
 macro_rules! header_name {
     ($bytes:expr) => {{
@@ -129,6 +130,9 @@ impl Http1Transaction for Server {
     fn parse(buf: &mut BytesMut, ctx: ParseContext<'_>) -> ParseResult<RequestLine> {
         debug_assert!(!buf.is_empty(), "parse called with empty buf");
 
+        let max_uri_len = ctx
+            .h1_max_uri_len
+            .map_or(DEFAULT_MAX_URI_LEN, |max| max.min(DEFAULT_MAX_URI_LEN));
         let mut keep_alive;
         let is_http_11;
         let subject;
@@ -161,7 +165,7 @@ impl Http1Transaction for Server {
                     trace!("Request.parse Complete({})", parsed_len);
                     len = parsed_len;
                     let uri = req.path.unwrap();
-                    if uri.len() > MAX_URI_LEN {
+                    if uri.len() > max_uri_len {
                         return Err(Parse::UriTooLong);
                     }
                     method = Method::from_bytes(req.method.unwrap().as_bytes())?;
@@ -179,7 +183,24 @@ impl Http1Transaction for Server {
                     record_header_indices(bytes, req.headers, &mut headers_indices)?;
                     headers_len = req.headers.len();
                 }
-                Ok(httparse::Status::Partial) => return Ok(None),
+                Ok(httparse::Status::Partial) => {
+                    // Don't wait for the whole head to be buffered to notice
+                    // the request target is already too long.
+                    let uri_len = match (req.method, req.path) {
+                        (_, Some(path)) => path.len(),
+                        (Some(method), None) => {
+                            // httparse skips leading empty lines, so count
+                            // from where the method actually starts.
+                            let method_start = method.as_ptr() as usize - bytes.as_ptr() as usize;
+                            bytes.len().saturating_sub(method_start + method.len() + 1)
+                        }
+                        (None, None) => 0,
+                    };
+                    if uri_len > max_uri_len {
+                        return Err(Parse::UriTooLong);
+                    }
+                    return Ok(None);
+                }
                 Err(err) => {
                     return Err(match err {
                         // if invalid Token, try to determine if for method or path
@@ -266,7 +287,7 @@ impl Http1Transaction for Server {
                     if is_te {
                         continue;
                     }
-                    let len = headers::content_length_parse(&value)
+                    let len = headers::content_length_parse_list(&value)
                         .ok_or_else(Parse::content_length_invalid)?;
                     if let Some(prev) = con_len {
                         if prev != len {
@@ -476,6 +497,10 @@ impl Http1Transaction for Server {
         true
     }
 
+    fn may_close_delimit(method: &Option<Method>, head: &MessageHead<Self::Outgoing>) -> bool {
+        Server::can_have_body(method, head.subject)
+    }
+
     fn update_date() {
         date::update();
     }
@@ -642,6 +667,7 @@ impl Server {
         let mut encoder = Encoder::length(0);
         let mut allowed_trailer_fields: Option<Vec<HeaderValue>> = None;
         let mut wrote_date = false;
+        let mut wrote_connection = false;
         let mut cur_name = None;
         let mut is_name_written = false;
         let mut must_write_chunked = false;
@@ -807,6 +833,7 @@ impl Server {
                     continue 'headers;
                 }
                 header::CONNECTION => {
+                    wrote_connection = true;
                     if !is_last && headers::connection_close(&value) {
                         is_last = true;
                     }
@@ -933,6 +960,21 @@ impl Server {
             encoder = Encoder::length(0);
         }
 
+        // If the connection won't be reused after this response, either
+        // because the body is delimited by closing it, or because keep-alive
+        // is off, let the client know, unless the user already said something.
+        if !wrote_connection
+            && (encoder.is_close_delimited() || !msg.keep_alive)
+            && msg.head.subject != StatusCode::SWITCHING_PROTOCOLS
+            && !(msg.req_method == &Some(Method::CONNECT) && msg.head.subject.is_success())
+        {
+            header_name_writer.write_full_header_line(
+                dst,
+                "connection: close\r\n",
+                (header::CONNECTION, ": close\r\n"),
+            );
+        }
+
         // cached date is much faster than formatting every request
         // don't force the write if disabled
         if !wrote_date && msg.date_header {
@@ -1647,6 +1689,7 @@ mod tests {
                 req_method: &mut method,
                 h1_parser_config: Default::default(),
                 h1_max_headers: None,
+                h1_max_uri_len: None,
                 h1_header_read_timeout: None,
                 h1_header_read_timeout_fut: &mut None,
                 h1_header_read_timeout_running: &mut false,
@@ -1670,6 +1713,36 @@ mod tests {
         assert_eq!(method, Some(crate::Method::GET));
     }
 
+    #[test]
+    fn test_parse_request_asterisk_form() {
+        let mut raw = BytesMut::from("OPTIONS * HTTP/1.1\r\nHost: hyper.rs\r\n\r\n");
+        let msg = Server::parse(
+            &mut raw,
+            ParseContext {
+                cached_headers: &mut None,
+                req_method: &mut None,
+                h1_parser_config: Default::default(),
+                h1_max_headers: None,
+                h1_max_uri_len: None,
+                h1_header_read_timeout: None,
+                h1_header_read_timeout_fut: &mut None,
+                h1_header_read_timeout_running: &mut false,
+                timer: Time::Empty,
+                preserve_header_case: false,
+                #[cfg(feature = "ffi")]
+                preserve_header_order: false,
+                h09_responses: false,
+                #[cfg(feature = "ffi")]
+                on_informational: &mut None,
+            },
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(msg.head.subject.0, crate::Method::OPTIONS);
+        assert_eq!(msg.head.subject.1, "*");
+        assert_eq!(msg.head.subject.1.path(), "*");
+    }
+
     #[test]
     fn test_parse_response() {
         let _ = pretty_env_logger::try_init();
@@ -1679,6 +1752,7 @@ mod tests {
             req_method: &mut Some(crate::Method::GET),
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1706,6 +1780,7 @@ mod tests {
             req_method: &mut None,
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1720,6 +1795,172 @@ mod tests {
         Server::parse(&mut raw, ctx).unwrap_err();
     }
 
+    #[test]
+    fn test_parse_request_max_uri_len() {
+        fn parse(s: &str) -> ParseResult<RequestLine> {
+            let mut raw = BytesMut::from(s);
+            let ctx = ParseContext {
+                cached_headers: &mut None,
+                req_method: &mut None,
+                h1_parser_config: Default::default(),
+                h1_max_headers: None,
+                h1_max_uri_len: Some(16),
+                h1_header_read_timeout: None,
+                h1_header_read_timeout_fut: &mut None,
+                h1_header_read_timeout_running: &mut false,
+                timer: Time::Empty,
+                preserve_header_case: false,
+                #[cfg(feature = "ffi")]
+                preserve_header_order: false,
+                h09_responses: false,
+                #[cfg(feature = "ffi")]
+                on_informational: &mut None,
+            };
+            Server::parse(&mut raw, ctx)
+        }
+
+        // exactly at the limit
+        let msg = parse("GET /aaaaaaaaaaaaaaa HTTP/1.1\r\n\r\n")
+            .expect("parse ok")
+            .expect("parse complete");
+        assert_eq!(msg.head.subject.1, "/aaaaaaaaaaaaaaa");
+
+        // one over the limit
+        match parse("GET /aaaaaaaaaaaaaaaa HTTP/1.1\r\n\r\n") {
+            Err(Parse::UriTooLong) => (),
+            other => panic!("expected UriTooLong, got {:?}", other),
+        }
+
+        // a partial request target is rejected as soon as it is over the
+        // limit, without waiting for the rest of the head
+        match parse("GET /aaaaaaaaaaaaaaaa") {
+            Err(Parse::UriTooLong) => (),
+            other => panic!("expected UriTooLong, got {:?}", other),
+        }
+
+        // while a partial one under the limit just needs more bytes
+        assert!(parse("GET /aaaaaaaaaa").expect("parse ok").is_none());
+
+        // a limit over what http::Uri accepts is capped to the default
+        let mut raw = BytesMut::from(format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(70_000)).as_str());
+        let ctx = ParseContext {
+            cached_headers: &mut None,
+            req_method: &mut None,
+            h1_parser_config: Default::default(),
+            h1_max_headers: None,
+            h1_max_uri_len: Some(100_000),
+            h1_header_read_timeout: None,
+            h1_header_read_timeout_fut: &mut None,
+            h1_header_read_timeout_running: &mut false,
+            timer: Time::Empty,
+            preserve_header_case: false,
+            #[cfg(feature = "ffi")]
+            preserve_header_order: false,
+            h09_responses: false,
+            #[cfg(feature = "ffi")]
+            on_informational: &mut None,
+        };
+        match Server::parse(&mut raw, ctx) {
+            Err(Parse::UriTooLong) => (),
+            other => panic!("expected UriTooLong, got {:?}", other),
+        }
+
+        // leading empty lines don't count towards the request target
+        assert!(parse("\r\n\r\nGET /aaaaaaaaaaaaaaa")
+            .expect("parse ok")
+            .is_none());
+
+        // a complete request target over the limit is rejected even if the
+        // rest of the head is still missing
+        match parse("GET /aaaaaaaaaaaaaaaa HTTP/1.1\r\nHost: ") {
+            Err(Parse::UriTooLong) => (),
+            other => panic!("expected UriTooLong, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_request_rejects_obs_fold() {
+        // Folding is only ever tolerated in responses, even if the parser
+        // config allows it there.
+        let mut h1_parser_config = httparse::ParserConfig::default();
+        h1_parser_config.allow_obsolete_multiline_headers_in_responses(true);
+        let mut raw = BytesMut::from(
+            "GET / HTTP/1.1\r\n\
+             X-Folded: Hello\r\n \
+             world\r\n\
+             \r\n",
+        );
+        let ctx = ParseContext {
+            cached_headers: &mut None,
+            req_method: &mut None,
+            h1_parser_config,
+            h1_max_headers: None,
+            h1_max_uri_len: None,
+            h1_header_read_timeout: None,
+            h1_header_read_timeout_fut: &mut None,
+            h1_header_read_timeout_running: &mut false,
+            timer: Time::Empty,
+            preserve_header_case: false,
+            #[cfg(feature = "ffi")]
+            preserve_header_order: false,
+            h09_responses: false,
+            #[cfg(feature = "ffi")]
+            on_informational: &mut None,
+        };
+        match Server::parse(&mut raw, ctx) {
+            Err(Parse::Header(_)) => (),
+            other => panic!("expected header error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_response_status_code_range() {
+        fn parse(s: &str) -> ParseResult<StatusCode> {
+            let mut raw = BytesMut::from(s);
+            let ctx = ParseContext {
+                cached_headers: &mut None,
+                req_method: &mut Some(crate::Method::GET),
+                h1_parser_config: Default::default(),
+                h1_max_headers: None,
+                h1_max_uri_len: None,
+                h1_header_read_timeout: None,
+                h1_header_read_timeout_fut: &mut None,
+                h1_header_read_timeout_running: &mut false,
+                timer: Time::Empty,
+                preserve_header_case: false,
+                #[cfg(feature = "ffi")]
+                preserve_header_order: false,
+                h09_responses: false,
+                #[cfg(feature = "ffi")]
+                on_informational: &mut None,
+            };
+            Client::parse(&mut raw, ctx)
+        }
+
+        let msg = parse("HTTP/1.1 599 Whatever\r\nContent-Length: 0\r\n\r\n")
+            .expect("parse ok")
+            .expect("parse complete");
+        assert_eq!(msg.head.subject.as_u16(), 599);
+
+        // Values from 600 to 999 are invalid per RFC 9110, but clients are
+        // told to treat them like a 5xx instead of failing the response. So
+        // they are passed along as-is.
+        let msg = parse("HTTP/1.1 600 Whatever\r\nContent-Length: 0\r\n\r\n")
+            .expect("parse ok")
+            .expect("parse complete");
+        assert_eq!(msg.head.subject.as_u16(), 600);
+
+        match parse("HTTP/1.1 099 Whatever\r\nContent-Length: 0\r\n\r\n") {
+            Err(Parse::Status) => (),
+            other => panic!("expected Parse::Status, got {:?}", other),
+        }
+
+        match parse("HTTP/1.1 1000 Whatever\r\nContent-Length: 0\r\n\r\n") {
+            Err(Parse::Status) => (),
+            other => panic!("expected Parse::Status, got {:?}", other),
+        }
+    }
+
     const H09_RESPONSE: &'static str = "Baguettes are super delicious, don't you agree?";
 
     #[test]
@@ -1731,6 +1972,7 @@ mod tests {
             req_method: &mut Some(crate::Method::GET),
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1758,6 +2000,7 @@ mod tests {
             req_method: &mut Some(crate::Method::GET),
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1789,6 +2032,7 @@ mod tests {
             req_method: &mut Some(crate::Method::GET),
             h1_parser_config,
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1817,6 +2061,7 @@ mod tests {
             req_method: &mut Some(crate::Method::GET),
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1840,6 +2085,7 @@ mod tests {
             req_method: &mut None,
             h1_parser_config: Default::default(),
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: None,
             h1_header_read_timeout_fut: &mut None,
             h1_header_read_timeout_running: &mut false,
@@ -1884,6 +2130,7 @@ mod tests {
                     req_method: &mut None,
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -1909,6 +2156,7 @@ mod tests {
                     req_method: &mut None,
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -2064,6 +2312,38 @@ mod tests {
             "multiple content-lengths",
         );
 
+        // a list of the same value in a single header is fine too
+        assert_eq!(
+            parse(
+                "\
+                 POST / HTTP/1.1\r\n\
+                 content-length: 10, 10\r\n\
+                 \r\n\
+                 "
+            )
+            .decode,
+            DecodedLength::new(10)
+        );
+
+        // but a list of different values is an error
+        parse_err(
+            "\
+             POST / HTTP/1.1\r\n\
+             content-length: 10, 11\r\n\
+             \r\n\
+             ",
+            "content-length list with different values",
+        );
+
+        parse_err(
+            "\
+             POST / HTTP/1.1\r\n\
+             content-length: 10,\r\n\
+             \r\n\
+             ",
+            "content-length list with empty value",
+        );
+
         // content-length with prefix is not allowed
         parse_err(
             "\
@@ -2143,6 +2423,7 @@ mod tests {
                     req_method: &mut Some(Method::GET),
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -2168,6 +2449,7 @@ mod tests {
                     req_method: &mut Some(m),
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -2193,6 +2475,7 @@ mod tests {
                     req_method: &mut Some(Method::GET),
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -2486,6 +2769,29 @@ mod tests {
         assert_eq!(unfold("obs\r\n fold\r\n\t line"), "obs fold line",);
     }
 
+    #[test]
+    fn test_client_request_encode_asterisk_form() {
+        let mut head = MessageHead::<RequestLine>::default();
+        head.subject.0 = Method::OPTIONS;
+        head.subject.1 = "*".parse().unwrap();
+
+        let mut vec = Vec::new();
+        Client::encode(
+            Encode {
+                head: &mut head,
+                body: None,
+                keep_alive: true,
+                req_method: &mut None,
+                title_case_headers: false,
+                date_header: true,
+            },
+            &mut vec,
+        )
+        .unwrap();
+
+        assert_eq!(vec, b"OPTIONS * HTTP/1.1\r\n\r\n".to_vec());
+    }
+
     #[test]
     fn test_client_request_encode_title_case() {
         use crate::proto::BodyLength;
@@ -2746,6 +3052,64 @@ mod tests {
         assert_eq!(&vec, &expected_response);
     }
 
+    #[test]
+    fn test_server_encode_keeps_user_date_header() {
+        use crate::proto::BodyLength;
+        use http::header::HeaderValue;
+
+        let mut head = MessageHead::default();
+        head.headers.insert(
+            "date",
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+
+        let mut vec = Vec::new();
+        Server::encode(
+            Encode {
+                head: &mut head,
+                body: Some(BodyLength::Known(0)),
+                keep_alive: true,
+                req_method: &mut None,
+                title_case_headers: false,
+                date_header: true,
+            },
+            &mut vec,
+        )
+        .unwrap();
+
+        let expected_response =
+            b"HTTP/1.1 200 OK\r\ndate: Sun, 06 Nov 1994 08:49:37 GMT\r\ncontent-length: 0\r\n\r\n";
+
+        assert_eq!(&vec, &expected_response);
+    }
+
+    #[test]
+    fn test_server_encode_rejects_content_length_list() {
+        use crate::proto::BodyLength;
+        use http::header::HeaderValue;
+
+        let mut head = MessageHead::default();
+        head.headers
+            .insert("content-length", HeaderValue::from_static("10, 10"));
+
+        let mut vec = Vec::new();
+        let err = Server::encode(
+            Encode {
+                head: &mut head,
+                body: Some(BodyLength::Unknown),
+                keep_alive: true,
+                req_method: &mut None,
+                title_case_headers: false,
+                date_header: true,
+            },
+            &mut vec,
+        )
+        .unwrap_err();
+
+        assert!(err.is_user());
+        assert!(vec.is_empty(), "nothing should be written: {:?}", vec);
+    }
+
     #[test]
     fn parse_header_htabs() {
         let mut bytes = BytesMut::from("HTTP/1.1 200 OK\r\nserver: hello\tworld\r\n\r\n");
@@ -2756,6 +3120,7 @@ mod tests {
                 req_method: &mut Some(Method::GET),
                 h1_parser_config: Default::default(),
                 h1_max_headers: None,
+                h1_max_uri_len: None,
                 h1_header_read_timeout: None,
                 h1_header_read_timeout_fut: &mut None,
                 h1_header_read_timeout_running: &mut false,
@@ -2803,6 +3168,7 @@ mod tests {
                         req_method: &mut None,
                         h1_parser_config: Default::default(),
                         h1_max_headers: max_headers,
+                        h1_max_uri_len: None,
                         h1_header_read_timeout: None,
                         h1_header_read_timeout_fut: &mut None,
                         h1_header_read_timeout_running: &mut false,
@@ -2831,6 +3197,7 @@ mod tests {
                         req_method: &mut None,
                         h1_parser_config: Default::default(),
                         h1_max_headers: max_headers,
+                        h1_max_uri_len: None,
                         h1_header_read_timeout: None,
                         h1_header_read_timeout_fut: &mut None,
                         h1_header_read_timeout_running: &mut false,
@@ -2982,6 +3349,7 @@ mod tests {
                     req_method: &mut None,
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
@@ -3031,6 +3399,7 @@ mod tests {
                     req_method: &mut None,
                     h1_parser_config: Default::default(),
                     h1_max_headers: None,
+                    h1_max_uri_len: None,
                     h1_header_read_timeout: None,
                     h1_header_read_timeout_fut: &mut None,
                     h1_header_read_timeout_running: &mut false,
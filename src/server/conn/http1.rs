@@ -75,9 +75,12 @@ pub struct Builder {
     h1_title_case_headers: bool,
     h1_preserve_header_case: bool,
     h1_max_headers: Option<usize>,
+    h1_max_uri_len: Option<usize>,
     h1_header_read_timeout: Dur,
+    h1_keep_alive_timeout: Dur,
     h1_writev: Option<bool>,
     max_buf_size: Option<usize>,
+    read_buf_init_size: Option<usize>,
     pipeline_flush: bool,
     date_header: bool,
 }
@@ -243,9 +246,12 @@ impl Builder {
             h1_title_case_headers: false,
             h1_preserve_header_case: false,
             h1_max_headers: None,
+            h1_max_uri_len: None,
             h1_header_read_timeout: Dur::Default(Some(Duration::from_secs(30))),
+            h1_keep_alive_timeout: Dur::Default(None),
             h1_writev: None,
             max_buf_size: None,
+            read_buf_init_size: None,
             pipeline_flush: false,
             date_header: true,
         }
@@ -314,6 +320,20 @@ impl Builder {
         self
     }
 
+    /// Set the maximum length of the request target (URI) in the request line.
+    ///
+    /// If the server receives a request target longer than this, it responds
+    /// to the client with "414 URI Too Long", without waiting for the rest
+    /// of the message head to arrive.
+    ///
+    /// Default is 65534 bytes, which is also the maximum, since longer
+    /// request targets can't be represented as a `Uri`. Larger values are
+    /// capped to it.
+    pub fn max_uri_len(&mut self, val: usize) -> &mut Self {
+        self.h1_max_uri_len = Some(val);
+        self
+    }
+
     /// Set a timeout for reading client request headers. If a client does not
     /// transmit the entire header within this time, the connection is closed.
     ///
@@ -328,6 +348,21 @@ impl Builder {
         self
     }
 
+    /// Set a timeout for how long a kept-alive connection may sit idle
+    /// between requests. If the client does not start a new request within
+    /// this time, the connection is closed, without an error.
+    ///
+    /// Requires a [`Timer`] set by [`Builder::timer`] to take effect. Panics if `keep_alive_timeout` is configured
+    /// without a [`Timer`].
+    ///
+    /// Pass `None` to disable.
+    ///
+    /// Default is `None`.
+    pub fn keep_alive_timeout(&mut self, timeout: impl Into<Option<Duration>>) -> &mut Self {
+        self.h1_keep_alive_timeout = Dur::Configured(timeout.into());
+        self
+    }
+
     /// Set whether HTTP/1 connections should try to use vectored writes,
     /// or always flatten into a single buffer.
     ///
@@ -361,6 +396,27 @@ impl Builder {
         self
     }
 
+    /// Set the size of the first read into the connection's read buffer.
+    ///
+    /// The read buffer still grows, up to `max_buf_size`, when larger
+    /// messages are received, and shrinks back to this size when they stop.
+    /// A smaller value saves memory on connections that only ever see small
+    /// requests.
+    ///
+    /// Default is 8192.
+    ///
+    /// # Panics
+    ///
+    /// The minimum value allowed is 64. This method panics if the passed `sz` is less than the minimum.
+    pub fn read_buf_init_size(&mut self, sz: usize) -> &mut Self {
+        assert!(
+            sz >= proto::h1::MINIMUM_READ_BUF_INIT_SIZE,
+            "the read_buf_init_size cannot be smaller than the minimum that h1 specifies."
+        );
+        self.read_buf_init_size = Some(sz);
+        self
+    }
+
     /// Set whether the `date` header should be included in HTTP responses.
     ///
     /// Note that including the `date` header is recommended by RFC 7231.
@@ -448,12 +504,21 @@ impl Builder {
         if let Some(max_headers) = self.h1_max_headers {
             conn.set_http1_max_headers(max_headers);
         }
+        if let Some(max_uri_len) = self.h1_max_uri_len {
+            conn.set_http1_max_uri_len(max_uri_len);
+        }
         if let Some(dur) = self
             .timer
             .check(self.h1_header_read_timeout, "header_read_timeout")
         {
             conn.set_http1_header_read_timeout(dur);
         };
+        if let Some(dur) = self
+            .timer
+            .check(self.h1_keep_alive_timeout, "keep_alive_timeout")
+        {
+            conn.set_http1_keep_alive_timeout(dur);
+        };
         if let Some(writev) = self.h1_writev {
             if writev {
                 conn.set_write_strategy_queue();
@@ -465,6 +530,9 @@ impl Builder {
         if let Some(max) = self.max_buf_size {
             conn.set_max_buf_size(max);
         }
+        if let Some(sz) = self.read_buf_init_size {
+            conn.set_read_buf_init_size(sz);
+        }
         let sd = proto::h1::dispatch::Server::new(service);
         let proto = proto::h1::Dispatcher::new(sd, conn);
         Connection { conn: proto }
@@ -1057,6 +1057,32 @@ test! {
             body: &b"hallo"[..],
 }
 
+test! {
+    name: client_response_http10_read_to_eof,
+
+    server:
+        expected: "\
+            GET /http10 HTTP/1.1\r\n\
+            host: {addr}\r\n\
+            \r\n\
+            ",
+        reply: "\
+            HTTP/1.0 200 OK\r\n\
+            \r\n\
+            hello world\
+            ",
+
+    client:
+        request: {
+            method: GET,
+            url: "http://{addr}/http10",
+        },
+        response:
+            status: OK,
+            headers: {},
+            body: &b"hello world"[..],
+}
+
 test! {
     name: client_pipeline_responses_extra,
 
@@ -2554,6 +2580,23 @@ mod conn {
         assert!(error.is_user());
     }
 
+    #[tokio::test]
+    async fn send_request_is_closed_when_connection_dropped() {
+        let (_listener, addr) = setup_tk_test_server().await;
+
+        let io = tcp_connect(&addr).await.expect("tcp connect");
+        let (client, conn) = conn::http1::handshake::<_, Empty<Bytes>>(io)
+            .await
+            .expect("handshake");
+
+        assert!(!client.is_closed());
+
+        drop(conn);
+
+        assert!(client.is_closed());
+        assert!(!client.is_ready());
+    }
+
     async fn drain_til_eof<T: tokio::io::AsyncRead + Unpin>(mut sock: T) -> io::Result<()> {
         let mut buf = [0u8; 1024];
         loop {
@@ -850,6 +850,156 @@ fn header_connection_close() {
     );
 }
 
+#[test]
+fn close_delimited_response_sends_connection_close() {
+    let server = serve();
+    let b = futures_util::stream::once(async { Ok::<_, BoxError>(Bytes::from("foo bar baz")) });
+    server.reply().body_stream(b);
+    let mut req = connect(server.addr());
+    req.write_all(
+        b"\
+        GET / HTTP/1.0\r\n\
+        Host: example.domain\r\n\
+        Connection: keep-alive\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 1");
+
+    // body is delimited by closing the connection, so read_to_end should
+    // succeed, and the client should have been told about it.
+    let mut buf = Vec::new();
+    req.read_to_end(&mut buf).expect("reading 1");
+    assert!(buf.ends_with(b"foo bar baz"));
+    let sbuf = s(&buf);
+    assert!(
+        sbuf.contains("connection: close\r\n"),
+        "response should have sent close: {:?}",
+        sbuf,
+    );
+}
+
+#[test]
+fn streamed_response_with_content_length_keeps_alive() {
+    let foo_bar = b"foo bar baz";
+    let server = serve();
+    let b = futures_util::stream::once(async { Ok::<_, BoxError>(Bytes::from("foo bar baz")) });
+    server
+        .reply()
+        .header("content-length", foo_bar.len().to_string())
+        .body_stream(b);
+    let mut req = connect(server.addr());
+    req.write_all(
+        b"\
+        GET / HTTP/1.0\r\n\
+        Host: example.domain\r\n\
+        Connection: keep-alive\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 1");
+
+    // the body has an explicit length, so the connection can be kept alive
+    let res = read_until(&mut req, |buf| buf.ends_with(foo_bar)).expect("reading 1");
+    let sres = s(&res);
+    assert!(
+        sres.contains("connection: keep-alive\r\n"),
+        "HTTP/1.0 response should have sent keep-alive: {:?}",
+        sres,
+    );
+
+    // try again!
+
+    let quux = b"zar quux";
+    server
+        .reply()
+        .header("content-length", quux.len().to_string())
+        .body(quux);
+    req.write_all(
+        b"\
+        GET /quux HTTP/1.0\r\n\
+        Host: example.domain\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 2");
+
+    read_until(&mut req, |buf| buf.ends_with(quux)).expect("reading 2");
+}
+
+#[test]
+fn streamed_head_response_keeps_alive() {
+    let server = serve();
+    let b = futures_util::stream::once(async { Ok::<_, BoxError>(Bytes::from("foo bar baz")) });
+    server.reply().body_stream(b);
+    let mut req = connect(server.addr());
+    req.write_all(
+        b"\
+        HEAD / HTTP/1.0\r\n\
+        Host: example.domain\r\n\
+        Connection: keep-alive\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 1");
+
+    // a HEAD response has no body, so it is never delimited by closing
+    let res = read_until(&mut req, |buf| buf.ends_with(b"\r\n\r\n")).expect("reading 1");
+    let sres = s(&res);
+    assert!(
+        sres.contains("connection: keep-alive\r\n"),
+        "HTTP/1.0 response should have sent keep-alive: {:?}",
+        sres,
+    );
+
+    // try again!
+
+    let quux = b"zar quux";
+    server
+        .reply()
+        .header("content-length", quux.len().to_string())
+        .body(quux);
+    req.write_all(
+        b"\
+        GET /quux HTTP/1.0\r\n\
+        Host: example.domain\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 2");
+
+    read_until(&mut req, |buf| buf.ends_with(quux)).expect("reading 2");
+}
+
+#[test]
+fn keep_alive_response_does_not_send_connection_close() {
+    let foo_bar = b"foo bar baz";
+    let server = serve();
+    server
+        .reply()
+        .header("content-length", foo_bar.len().to_string())
+        .body(foo_bar);
+    let mut req = connect(server.addr());
+    req.write_all(
+        b"\
+        GET / HTTP/1.1\r\n\
+        Host: example.domain\r\n\
+        \r\n\
+    ",
+    )
+    .expect("writing 1");
+
+    let mut buf = [0; 256];
+    let n = req.read(&mut buf).expect("reading 1");
+    let sbuf = s(&buf[..n]);
+    assert!(sbuf.ends_with("foo bar baz"), "read: {:?}", sbuf);
+    assert!(
+        !sbuf.contains("connection:"),
+        "response shouldn't have sent a connection header: {:?}",
+        sbuf,
+    );
+}
+
 #[test]
 fn expect_continue_sends_100() {
     let server = serve();
@@ -1117,6 +1267,7 @@ fn pipeline_enabled() {
 
         assert_eq!(s(lines.next().unwrap()), "HTTP/1.1 200 OK\r");
         assert_eq!(s(lines.next().unwrap()), "content-length: 12\r");
+        assert_eq!(s(lines.next().unwrap()), "connection: close\r");
         lines.next().unwrap(); // Date
         assert_eq!(s(lines.next().unwrap()), "\r");
         assert_eq!(s(lines.next().unwrap()), "Hello World");
@@ -1256,6 +1407,52 @@ async fn disable_keep_alive_post_request() {
     child.join().unwrap();
 }
 
+#[tokio::test]
+async fn unread_request_body_is_drained_for_keep_alive() {
+    let (listener, addr) = setup_tcp_listener();
+
+    let child = thread::spawn(move || {
+        let mut req = connect(&addr);
+        // the service never reads this body
+        req.write_all(
+            b"\
+            POST / HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Content-Length: 5\r\n\
+            \r\n\
+            hello\
+        ",
+        )
+        .unwrap();
+        read_until(&mut req, |buf| buf.ends_with(HELLO.as_bytes())).expect("reading 1");
+
+        // so the connection can only be reused if it was skipped over
+        req.write_all(
+            b"\
+            GET / HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Connection: close\r\n\
+            \r\n\
+        ",
+        )
+        .unwrap();
+        let mut buf = Vec::new();
+        req.read_to_end(&mut buf).expect("reading 2");
+        assert!(
+            buf.starts_with(b"HTTP/1.1 200 OK\r\n"),
+            "second response: {:?}",
+            s(&buf)
+        );
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    http1::Builder::new()
+        .serve_connection(TokioIo::new(socket), HelloWorld)
+        .await
+        .expect("serve_connection");
+    child.join().unwrap();
+}
+
 #[tokio::test]
 async fn http1_graceful_shutdown_after_upgrade() {
     let (listener, addr) = setup_tcp_listener();
@@ -1584,6 +1781,58 @@ async fn header_read_timeout_slow_writes_multiple_requests() {
     conn.without_shutdown().await.expect_err("header timeout");
 }
 
+#[tokio::test]
+async fn keep_alive_timeout_closes_idle_connection() {
+    let (listener, addr) = setup_tcp_listener();
+
+    let client = thread::spawn(move || {
+        let mut tcp = connect(&addr);
+        tcp.write_all(
+            b"\
+            GET / HTTP/1.1\r\n\
+            Host: example.domain\r\n\
+            \r\n\
+        ",
+        )
+        .expect("write 1");
+        let mut buf = [0; 256];
+        let n = tcp.read(&mut buf).expect("read 1");
+        assert!(s(&buf[..n]).starts_with("HTTP/1.1 200 OK\r\n"));
+
+        thread::sleep(Duration::from_millis(500));
+
+        // the connection sat idle for longer than the timeout, so the
+        // server should have closed it instead of serving another request
+        let _ = tcp.write_all(
+            b"\
+            GET / HTTP/1.1\r\n\
+            Host: example.domain\r\n\
+            \r\n\
+        ",
+        );
+        let n = tcp.read(&mut buf).unwrap_or(0);
+        assert_eq!(n, 0, "second request should not be served");
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    let socket = TokioIo::new(socket);
+    let conn = http1::Builder::new()
+        .timer(TokioTimer)
+        .keep_alive_timeout(Duration::from_millis(100))
+        .serve_connection(
+            socket,
+            service_fn(|_| {
+                let res = Response::builder()
+                    .status(200)
+                    .body(Empty::<Bytes>::new())
+                    .unwrap();
+                future::ready(Ok::<_, hyper::Error>(res))
+            }),
+        );
+    conn.await.expect("idle close is not an error");
+    client.join().expect("client thread");
+}
+
 #[tokio::test]
 async fn upgrades() {
     let (listener, addr) = setup_tcp_listener();
@@ -2274,6 +2523,97 @@ async fn max_buf_size() {
         .expect_err("should TooLarge error");
 }
 
+#[cfg(feature = "http1")]
+#[tokio::test]
+async fn max_uri_len() {
+    let (listener, addr) = setup_tcp_listener();
+
+    const MAX: usize = 1024;
+
+    thread::spawn(move || {
+        let mut tcp = connect(&addr);
+        // the rest of the request line never arrives
+        tcp.write_all(b"GET /").expect("write 1");
+        tcp.write_all(&[b'a'; MAX]).expect("write 2");
+        let mut buf = [0; 256];
+        tcp.read(&mut buf).expect("read 1");
+
+        let expected = "HTTP/1.1 414 ";
+        assert_eq!(s(&buf[..expected.len()]), expected);
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    let socket = TokioIo::new(socket);
+    http1::Builder::new()
+        .max_uri_len(MAX)
+        .serve_connection(socket, HelloWorld)
+        .await
+        .expect_err("should UriTooLong error");
+}
+
+#[cfg(feature = "http1")]
+#[test]
+#[should_panic]
+fn read_buf_init_size_panic_too_small() {
+    const SZ: usize = 63;
+    http1::Builder::new().read_buf_init_size(SZ);
+}
+
+#[cfg(feature = "http1")]
+#[test]
+fn read_buf_init_size_no_panic() {
+    const SZ: usize = 64;
+    http1::Builder::new().read_buf_init_size(SZ);
+}
+
+#[cfg(feature = "http1")]
+#[tokio::test]
+async fn read_buf_init_size_eof() {
+    let (listener, addr) = setup_tcp_listener();
+
+    thread::spawn(move || {
+        // connect and hang up without sending anything
+        let _ = connect(&addr);
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    let socket = TokioIo::new(socket);
+    http1::Builder::new()
+        .read_buf_init_size(64)
+        .serve_connection(socket, HelloWorld)
+        .await
+        .expect("empty connection is not an error");
+}
+
+#[cfg(feature = "http1")]
+#[tokio::test]
+async fn read_buf_init_size() {
+    let (listener, addr) = setup_tcp_listener();
+
+    thread::spawn(move || {
+        let mut tcp = connect(&addr);
+        // a head several times larger than the first read
+        let req = format!(
+            "GET / HTTP/1.1\r\nx-big: {}\r\nconnection: close\r\n\r\n",
+            "a".repeat(1024)
+        );
+        tcp.write_all(req.as_bytes()).expect("write 1");
+        let mut buf = [0; 256];
+        tcp.read(&mut buf).expect("read 1");
+
+        let expected = "HTTP/1.1 200 OK\r\n";
+        assert_eq!(s(&buf[..expected.len()]), expected);
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    let socket = TokioIo::new(socket);
+    http1::Builder::new()
+        .read_buf_init_size(64)
+        .serve_connection(socket, HelloWorld)
+        .await
+        .expect("serve_connection");
+}
+
 #[cfg(feature = "http1")]
 #[tokio::test]
 async fn graceful_shutdown_before_first_request_no_block() {
@@ -2322,7 +2662,7 @@ fn streaming_body() {
         buf.starts_with(b"HTTP/1.1 200 OK\r\n"),
         "response is 200 OK"
     );
-    assert_eq!(buf.len(), 100_789, "full streamed body read");
+    assert_eq!(buf.len(), 100_808, "full streamed body read");
 }
 
 #[test]